@@ -1,22 +1,183 @@
 
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::collections::HashSet;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use actix_web::{web, App, HttpServer, HttpResponse, Result, middleware::Logger};
+use actix_web::{web, App, HttpServer, HttpRequest, HttpResponse, Result, middleware::Logger};
 use actix_files::Files;
 use serde::{Deserialize, Serialize};
-use pulldown_cmark::{Parser, html};
+use pulldown_cmark::{Parser, html, Event, Tag, CodeBlockKind, HeadingLevel, Options};
 use tera::{Tera, Context};
 use notify::{Watcher, RecommendedWatcher, Config};
 use tokio::time::sleep;
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use once_cell::sync::Lazy;
+use syntect::parsing::SyntaxSet;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{highlighted_html_for_string, ClassedHTMLGenerator, ClassStyle};
+use syntect::util::LinesWithEndings;
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// Controls how fenced code blocks are highlighted. Loaded once at startup so
+/// reloads triggered by the file watcher don't redo the (relatively expensive)
+/// syntax/theme setup on every edit.
+#[derive(Debug, Clone)]
+struct HighlightConfig {
+    theme_name: String,
+    inline_styles: bool,
+}
+
+impl Default for HighlightConfig {
+    fn default() -> Self {
+        HighlightConfig {
+            theme_name: "InspiredGitHub".to_string(),
+            inline_styles: true,
+        }
+    }
+}
+
+impl HighlightConfig {
+    fn from_env() -> Self {
+        let mut config = HighlightConfig::default();
+        if let Ok(theme) = std::env::var("MD_HIGHLIGHT_THEME") {
+            config.theme_name = theme;
+        }
+        if let Ok(inline) = std::env::var("MD_HIGHLIGHT_INLINE_STYLES") {
+            config.inline_styles = inline != "false" && inline != "0";
+        }
+        config
+    }
+}
+
+/// Which pulldown-cmark (GFM-ish) extensions are enabled during parsing.
+#[derive(Debug, Clone)]
+struct MarkdownOptions {
+    options: Options,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TASKLISTS);
+        options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+        MarkdownOptions { options }
+    }
+}
+
+impl MarkdownOptions {
+    fn from_env() -> Self {
+        let Ok(list) = std::env::var("MD_EXTENSIONS") else {
+            return MarkdownOptions::default();
+        };
+        let mut options = Options::empty();
+        for name in list.split(',').map(|s| s.trim()) {
+            match name {
+                "tables" => options.insert(Options::ENABLE_TABLES),
+                "footnotes" => options.insert(Options::ENABLE_FOOTNOTES),
+                "strikethrough" => options.insert(Options::ENABLE_STRIKETHROUGH),
+                "tasklists" => options.insert(Options::ENABLE_TASKLISTS),
+                "heading_attributes" => options.insert(Options::ENABLE_HEADING_ATTRIBUTES),
+                _ => {}
+            }
+        }
+        MarkdownOptions { options }
+    }
+}
+
+fn heading_level_num(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// Turns heading text into a URL-safe, lowercase anchor id (e.g. "Hello, World!" -> "hello-world").
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    let mut last_was_dash = false;
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+            last_was_dash = false;
+        } else if !slug.is_empty() && !last_was_dash {
+            slug.push('-');
+            last_was_dash = true;
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+/// Highlights a fenced code block's contents for the given language token,
+/// emitting either inline-styled `<pre>`/`<span style="...">` HTML or
+/// CSS-class-based HTML depending on `config.inline_styles`.
+fn highlight_code(code: &str, lang: &str, config: &HighlightConfig) -> String {
+    let syntax = SYNTAX_SET
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+
+    if config.inline_styles {
+        let theme = THEME_SET
+            .themes
+            .get(&config.theme_name)
+            .unwrap_or(&THEME_SET.themes["InspiredGitHub"]);
+        highlighted_html_for_string(code, &SYNTAX_SET, syntax, theme)
+            .unwrap_or_else(|_| format!("<pre><code>{}</code></pre>", escape_xml(code)))
+    } else {
+        let mut generator =
+            ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, ClassStyle::Spaced);
+        for line in LinesWithEndings::from(code) {
+            let _ = generator.parse_html_for_line_which_includes_newline(line);
+        }
+        format!("<pre class=\"code\"><code>{}</code></pre>", generator.finalize())
+    }
+}
 
 #[derive(Debug, Deserialize, Serialize)]
 struct FrontMatter {
     title: String,
     date: String,
     tags: Option<Vec<String>>,
+    draft: Option<bool>,
+    aliases: Option<Vec<String>>,
+}
+
+/// Parses a front matter `date` into a concrete point in time, accepting both
+/// RFC-3339 timestamps and bare `YYYY-MM-DD` dates (treated as local midnight).
+/// Falls back to the current time if the date can't be parsed, so an unparsable
+/// date behaves like "just published" rather than crashing the loader.
+fn parse_date(date_str: &str) -> DateTime<Local> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(date_str) {
+        return dt.with_timezone(&Local);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+        if let Some(naive) = date.and_hms_opt(0, 0, 0) {
+            if let Some(dt) = Local.from_local_datetime(&naive).single() {
+                return dt;
+            }
+        }
+    }
+    Local::now()
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct HeadingEntry {
+    id: String,
+    text: String,
+    level: u8,
 }
 
 #[derive(Debug, Serialize)]
@@ -25,6 +186,173 @@ struct Post {
     frontmatter: FrontMatter,
     content: String,
     html: String,
+    toc: Vec<HeadingEntry>,
+    /// Slugs of other posts whose `[[wikilinks]]` resolve to this post.
+    backlinks: Vec<String>,
+    /// Raw `target` of every `[[wikilink]]` found in this post's source,
+    /// captured at parse time so backlinks can be rebuilt from structured
+    /// data instead of re-scanning already-resolved HTML.
+    wikilink_targets: Vec<String>,
+}
+
+// Private-use-area sentinels used to round-trip an unresolved `[[slug|text]]`
+// wikilink through the rendered HTML until `resolve_wikilinks` can see the
+// full set of known slugs and rewrite it into a real link.
+const WIKILINK_OPEN: char = '\u{E000}';
+const WIKILINK_SEP: char = '\u{E001}';
+const WIKILINK_CLOSE: char = '\u{E002}';
+
+/// Rewrites `[[slug]]` / `[[slug|display text]]` occurrences on a single raw
+/// source line into a wikilink sentinel, to be resolved once every post's
+/// slug is known (see `resolve_wikilinks`).
+///
+/// This has to run over the raw Markdown *source*, not over parsed
+/// `Event::Text` fragments: pulldown-cmark's inline scanner treats `[` and
+/// `]` as link-opener/closer tokens, so a `[[slug]]` in real post text comes
+/// out as separate `"["`, `"["`, `"slug"`, `"]"`, `"]"` events rather than
+/// one contiguous text run, and matching within a single event never sees
+/// the two consecutive brackets.
+fn rewrite_wikilinks_in_line(line: &str, wikilink_targets: &mut Vec<String>) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+    loop {
+        let Some(start) = rest.find("[[") else {
+            output.push_str(rest);
+            break;
+        };
+        let Some(end_rel) = rest[start..].find("]]") else {
+            output.push_str(rest);
+            break;
+        };
+        let end = start + end_rel;
+
+        output.push_str(&rest[..start]);
+        let inner = &rest[start + 2..end];
+        let (target, display) = match inner.split_once('|') {
+            Some((target, display)) => (target.trim(), display.trim()),
+            None => (inner.trim(), inner.trim()),
+        };
+        wikilink_targets.push(target.to_string());
+        output.push_str(&format!(
+            "{}{}{}{}{}",
+            WIKILINK_OPEN, target, WIKILINK_SEP, display, WIKILINK_CLOSE
+        ));
+        rest = &rest[end + 2..];
+    }
+    output
+}
+
+/// Runs `rewrite_wikilinks_in_line` over every line of a post's raw Markdown
+/// source, skipping fenced code blocks so a literal `[[...]]` inside a code
+/// sample isn't mistaken for a wikilink. Fences are tracked line by line: any
+/// line starting with ```` ``` ```` or `~~~` toggles the skip. Indented code
+/// blocks and inline code spans are not skipped.
+fn rewrite_wikilinks_in_source(content: &str, wikilink_targets: &mut Vec<String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut in_fenced_code = false;
+    for (i, line) in content.split('\n').enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_fenced_code = !in_fenced_code;
+            output.push_str(line);
+        } else if in_fenced_code {
+            output.push_str(line);
+        } else {
+            output.push_str(&rewrite_wikilinks_in_line(line, wikilink_targets));
+        }
+    }
+    output
+}
+
+/// Second pass over every loaded post: resolves the wikilink sentinels left
+/// by `rewrite_wikilinks_in_source` now that the full slug set is known, and
+/// builds each post's `backlinks` from every post's `wikilink_targets`.
+///
+/// Backlinks are derived from the structured `wikilink_targets` captured at
+/// parse time rather than by re-scanning `html`, since the sentinels in
+/// `html` are consumed by this same pass — rescanning would only see
+/// outgoing links for posts resolved in the current call, silently
+/// forgetting backlinks for every other post on a second, incremental call.
+fn resolve_wikilinks(posts: &mut [Post]) {
+    let known_slugs: HashSet<String> = posts.iter().map(|post| post.slug.clone()).collect();
+    let mut backlinks: HashMap<String, Vec<String>> = HashMap::new();
+    for post in posts.iter() {
+        for target in &post.wikilink_targets {
+            if known_slugs.contains(target) {
+                backlinks.entry(target.clone()).or_default().push(post.slug.clone());
+            }
+        }
+    }
+
+    for post in posts.iter_mut() {
+        let mut resolved = String::with_capacity(post.html.len());
+        let mut rest = post.html.as_str();
+        while let Some(start) = rest.find(WIKILINK_OPEN) {
+            resolved.push_str(&rest[..start]);
+            let after_open = &rest[start + WIKILINK_OPEN.len_utf8()..];
+
+            // A stray, unpaired sentinel character (e.g. a private-use-area
+            // codepoint an author legitimately typed) is not our fault to
+            // crash over — fall back to treating it as literal text and
+            // keep scanning for the next real sentinel pair.
+            let Some(sep) = after_open.find(WIKILINK_SEP) else {
+                resolved.push(WIKILINK_OPEN);
+                rest = after_open;
+                continue;
+            };
+            let target = &after_open[..sep];
+            let after_sep = &after_open[sep + WIKILINK_SEP.len_utf8()..];
+            let Some(close) = after_sep.find(WIKILINK_CLOSE) else {
+                resolved.push(WIKILINK_OPEN);
+                resolved.push_str(target);
+                resolved.push(WIKILINK_SEP);
+                rest = after_sep;
+                continue;
+            };
+            let display = &after_sep[..close];
+
+            let target_escaped = escape_xml(target);
+            let display_escaped = escape_xml(display);
+            if known_slugs.contains(target) {
+                resolved.push_str(&format!(
+                    r#"<a href="/posts/{}" class="wikilink">{}</a>"#,
+                    target_escaped, display_escaped
+                ));
+            } else {
+                resolved.push_str(&format!(
+                    r#"<span class="wikilink wikilink-broken">{}</span>"#,
+                    display_escaped
+                ));
+            }
+
+            rest = &after_sep[close + WIKILINK_CLOSE.len_utf8()..];
+        }
+        resolved.push_str(rest);
+        post.html = resolved;
+    }
+
+    for post in posts.iter_mut() {
+        post.backlinks = backlinks.remove(&post.slug).unwrap_or_default();
+    }
+}
+
+impl FrontMatter {
+    fn published_at(&self) -> DateTime<Local> {
+        parse_date(&self.date)
+    }
+}
+
+impl Post {
+    /// A post is hidden from listings if it's explicitly marked `draft: true`,
+    /// or if its publish date is still in the future.
+    fn is_published(&self) -> bool {
+        if self.frontmatter.draft == Some(true) {
+            return false;
+        }
+        self.frontmatter.published_at() <= Local::now()
+    }
 }
 
 #[derive(Deserialize)]
@@ -33,7 +361,52 @@ struct SearchQuery {
     tag: Option<String>,
 }
 
-fn parse_post(path: &Path) -> Option<Post> {
+const SITE_URL: &str = "http://127.0.0.1:8080";
+
+#[derive(Serialize)]
+struct JsonFeed {
+    version: String,
+    title: String,
+    home_page_url: String,
+    feed_url: String,
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem {
+    id: String,
+    url: String,
+    title: String,
+    content_html: String,
+    date_published: String,
+}
+
+/// Walks the heading events once to collect their rendered text, so ids can
+/// be assigned (and de-duplicated) before the real render pass rewrites them.
+fn collect_heading_texts(content: &str, markdown_options: &MarkdownOptions) -> Vec<(HeadingLevel, Option<String>, String)> {
+    let mut headings = Vec::new();
+    let mut current: Option<(HeadingLevel, Option<String>, String)> = None;
+
+    for event in Parser::new_ext(content, markdown_options.options) {
+        match event {
+            Event::Start(Tag::Heading(level, id, _classes)) => {
+                current = Some((level, id.map(|s| s.to_string()), String::new()));
+            }
+            Event::Text(text) | Event::Code(text) if current.is_some() => {
+                current.as_mut().unwrap().2.push_str(&text);
+            }
+            Event::End(Tag::Heading(..)) => {
+                if let Some(heading) = current.take() {
+                    headings.push(heading);
+                }
+            }
+            _ => {}
+        }
+    }
+    headings
+}
+
+fn parse_post(path: &Path, highlight_config: &HighlightConfig, markdown_options: &MarkdownOptions) -> Option<Post> {
     let raw = fs::read_to_string(path).ok()?;
     let parts: Vec<&str> = raw.splitn(3, "---").collect();
     if parts.len() < 3 {
@@ -42,60 +415,203 @@ fn parse_post(path: &Path) -> Option<Post> {
     let fm_str = parts[1];
     let content = parts[2].trim().to_string();
     let frontmatter: FrontMatter = serde_yaml::from_str(fm_str).ok()?;
-    let parser = Parser::new(&content);
+
+    // Resolve wikilinks against the raw source, before it ever reaches the
+    // parser's inline scanner (see `rewrite_wikilinks_in_source`).
+    let mut wikilink_targets = Vec::new();
+    let markdown_source = rewrite_wikilinks_in_source(&content, &mut wikilink_targets);
+
+    // Assign (and de-dup) anchor ids for every heading up front. This reads
+    // `content`, not `markdown_source`: the latter has wikilinks already
+    // rewritten into WIKILINK_OPEN/SEP/CLOSE sentinels, and a heading's
+    // accumulated text would otherwise slugify the target and display text
+    // merged together instead of the heading's actual words.
+    let mut toc = Vec::new();
+    let mut used_ids: HashMap<String, u32> = HashMap::new();
+    for (level, explicit_id, text) in collect_heading_texts(&content, markdown_options) {
+        let base_id = explicit_id.unwrap_or_else(|| slugify(&text));
+        let id = match used_ids.get_mut(&base_id) {
+            Some(count) => {
+                *count += 1;
+                format!("{}-{}", base_id, count)
+            }
+            None => {
+                used_ids.insert(base_id.clone(), 1);
+                base_id
+            }
+        };
+        toc.push(HeadingEntry {
+            id,
+            text,
+            level: heading_level_num(level),
+        });
+    }
+
+    let parser = Parser::new_ext(&markdown_source, markdown_options.options);
+    let mut events = Vec::new();
+    let mut in_code_block = false;
+    let mut code_lang = String::new();
+    let mut code_body = String::new();
+    let mut heading_iter = toc.iter();
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::Heading(level, _id, classes)) => {
+                // Rendered manually (instead of left as Tag::Heading) so the
+                // de-duplicated anchor id assigned above (from the heading
+                // texts collect_heading_texts gathered) makes it into the output; the matching End(Heading) event
+                // still closes the tag via the default renderer.
+                let id = heading_iter.next().map(|h| h.id.as_str()).unwrap_or_default();
+                let class_attr = if classes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" class=\"{}\"", classes.join(" "))
+                };
+                events.push(Event::Html(
+                    format!("<h{} id=\"{}\"{}>", heading_level_num(level), id, class_attr).into(),
+                ));
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
+                in_code_block = true;
+                code_lang = lang.to_string();
+                code_body.clear();
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Indented)) => {
+                in_code_block = true;
+                code_lang.clear();
+                code_body.clear();
+            }
+            Event::Text(text) if in_code_block => {
+                code_body.push_str(&text);
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                in_code_block = false;
+                let highlighted = highlight_code(&code_body, &code_lang, highlight_config);
+                events.push(Event::Html(highlighted.into()));
+            }
+            other => events.push(other),
+        }
+    }
+
     let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
+    html::push_html(&mut html_output, events.into_iter());
+
     let slug = path.file_stem()?.to_string_lossy().to_string();
     Some(Post {
         slug,
         frontmatter,
         content,
         html: html_output,
+        toc,
+        backlinks: Vec::new(),
+        wikilink_targets,
     })
 }
 
-fn load_posts() -> Vec<Post> {
+fn load_posts(highlight_config: &HighlightConfig, markdown_options: &MarkdownOptions) -> Vec<Post> {
     let content_dir = "content";
     let mut posts = Vec::new();
     if let Ok(entries) = fs::read_dir(content_dir) {
         for entry in entries.flatten() {
             let path = entry.path();
             if path.extension().map(|e| e == "md").unwrap_or(false) {
-                if let Some(post) = parse_post(&path) {
+                if let Some(post) = parse_post(&path, highlight_config, markdown_options) {
                     println!("Loaded post: {}", post.frontmatter.title);
                     posts.push(post);
                 }
             }
         }
     }
-    // Sort posts by date (newest first)
-    posts.sort_by(|a, b| b.frontmatter.date.cmp(&a.frontmatter.date));
+    // Sort posts by parsed publish date (newest first)
+    posts.sort_by_key(|post| std::cmp::Reverse(post.frontmatter.published_at()));
+    resolve_wikilinks(&mut posts);
     posts
 }
 
+/// Builds an alias path -> slug map from every post's `aliases` front matter,
+/// so renamed posts can keep serving their old URLs via redirect.
+fn build_alias_map(post_cache: &HashMap<String, Post>) -> HashMap<String, String> {
+    let mut aliases = HashMap::new();
+    for post in post_cache.values() {
+        if let Some(post_aliases) = &post.frontmatter.aliases {
+            for alias in post_aliases {
+                aliases.insert(alias.trim_start_matches('/').to_string(), post.slug.clone());
+            }
+        }
+    }
+    aliases
+}
+
+/// Tag -> slugs (newest post first). Aliased since it's threaded through the
+/// shared `Arc<Mutex<_>>` cache and several handler signatures.
+type TagMap = HashMap<String, Vec<String>>;
+type TagCache = Arc<Mutex<TagMap>>;
+
+/// Builds a tag -> slugs map (newest post first) from every published post,
+/// so `/tags` and `/tags/{tag}` don't need to rescan every post per request.
+fn build_tag_map(post_cache: &HashMap<String, Post>) -> TagMap {
+    let mut posts: Vec<&Post> = post_cache.values().filter(|post| post.is_published()).collect();
+    posts.sort_by_key(|post| std::cmp::Reverse(post.frontmatter.published_at()));
+
+    let mut tags: TagMap = HashMap::new();
+    for post in posts {
+        if let Some(post_tags) = &post.frontmatter.tags {
+            for tag in post_tags {
+                tags.entry(tag.clone()).or_default().push(post.slug.clone());
+            }
+        }
+    }
+    tags
+}
+
 fn main() {
     println!("Starting markdown blog server...");
-    
-    let posts = load_posts();
-    if let Err(e) = start_server(posts) {
+
+    let highlight_config = HighlightConfig::from_env();
+    let markdown_options = MarkdownOptions::from_env();
+    let posts = load_posts(&highlight_config, &markdown_options);
+    if let Err(e) = start_server(posts, highlight_config, markdown_options) {
         eprintln!("Server error: {}", e);
         std::process::exit(1);
     }
 }
 
 #[actix_web::main]
-async fn start_server(initial_posts: Vec<Post>) -> std::io::Result<()> {
+async fn start_server(
+    initial_posts: Vec<Post>,
+    highlight_config: HighlightConfig,
+    markdown_options: MarkdownOptions,
+) -> std::io::Result<()> {
     // Create shared post cache
     let mut post_cache = HashMap::new();
     for post in initial_posts {
         post_cache.insert(post.slug.clone(), post);
     }
+    let alias_cache = build_alias_map(&post_cache);
+    let tag_cache = build_tag_map(&post_cache);
+    let search_index = build_search_index(&post_cache);
     let post_cache = Arc::new(Mutex::new(post_cache));
-    
+    let alias_cache = Arc::new(Mutex::new(alias_cache));
+    let tag_cache = Arc::new(Mutex::new(tag_cache));
+    let search_index = Arc::new(Mutex::new(search_index));
+
     // Start file watcher in background
     let cache_for_watcher = post_cache.clone();
+    let aliases_for_watcher = alias_cache.clone();
+    let tags_for_watcher = tag_cache.clone();
+    let search_index_for_watcher = search_index.clone();
+    let highlight_config_for_watcher = highlight_config.clone();
+    let markdown_options_for_watcher = markdown_options.clone();
     tokio::spawn(async move {
-        watch_files(cache_for_watcher).await;
+        watch_files(
+            cache_for_watcher,
+            aliases_for_watcher,
+            tags_for_watcher,
+            search_index_for_watcher,
+            highlight_config_for_watcher,
+            markdown_options_for_watcher,
+        )
+        .await;
     });
     
     // Initialize templates
@@ -109,22 +625,49 @@ async fn start_server(initial_posts: Vec<Post>) -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(post_cache.clone()))
+            .app_data(web::Data::new(alias_cache.clone()))
+            .app_data(web::Data::new(tag_cache.clone()))
+            .app_data(web::Data::new(search_index.clone()))
             .app_data(web::Data::new(tera.clone()))
             .wrap(Logger::default())
             .route("/", web::get().to(home))
             .route("/search", web::get().to(search))
             .route("/about", web::get().to(about))
             .route("/posts/{slug}", web::get().to(post_detail))
+            .route("/feed.xml", web::get().to(rss_feed))
+            .route("/atom.xml", web::get().to(atom_feed))
+            .route("/feed.json", web::get().to(json_feed))
+            .route("/tags", web::get().to(tags_index))
+            .route("/tags/{tag}/feed.xml", web::get().to(tag_feed))
+            .route("/tags/{tag}", web::get().to(tag_archive))
             .service(Files::new("/static", "static"))
+            .default_service(web::route().to(alias_fallback))
     })
     .bind("127.0.0.1:8080")?
     .run()
     .await
 }
 
-async fn watch_files(post_cache: Arc<Mutex<HashMap<String, Post>>>) {
+/// Records a single filesystem event's kind against every path it touched,
+/// so a burst of events within the debounce window collapses to the most
+/// recent `EventKind` seen per path — a later `Modify` isn't mistaken for
+/// the `Remove` that preceded it (or vice versa) within the same window.
+fn record_path_event(path_events: &mut HashMap<PathBuf, notify::EventKind>, event: notify::Event) {
+    for path in event.paths {
+        path_events.insert(path, event.kind);
+    }
+}
+
+async fn watch_files(
+    post_cache: Arc<Mutex<HashMap<String, Post>>>,
+    alias_cache: Arc<Mutex<HashMap<String, String>>>,
+    tag_cache: TagCache,
+    search_index: Arc<Mutex<SearchIndex>>,
+    highlight_config: HighlightConfig,
+    markdown_options: MarkdownOptions,
+) {
     use notify::EventKind;
-    
+
     let (tx, mut rx) = tokio::sync::mpsc::channel(100);
     
     let mut watcher = RecommendedWatcher::new(
@@ -145,45 +688,86 @@ async fn watch_files(post_cache: Arc<Mutex<HashMap<String, Post>>>) {
     
     println!("Watching content directory for changes...");
     
-    while let Some(_event) = rx.recv().await {
-        // Add a small delay to avoid processing rapid file changes
-        sleep(Duration::from_millis(100)).await;
-        
-        println!("Content changed, reloading posts...");
-        let new_posts = load_posts();
-        
-        let mut cache = post_cache.lock().unwrap();
-        cache.clear();
-        for post in new_posts {
-            cache.insert(post.slug.clone(), post);
+    while let Some(first_event) = rx.recv().await {
+        // Debounce for 100ms, coalescing every path touched by events that
+        // arrive within the window so a burst of edits does one update pass.
+        // Track the most recent EventKind seen per path so a later Modify
+        // doesn't get mistaken for the Remove that preceded it (or vice
+        // versa) within the same window.
+        let mut path_events: HashMap<PathBuf, EventKind> = HashMap::new();
+        record_path_event(&mut path_events, first_event);
+        let debounce = sleep(Duration::from_millis(100));
+        tokio::pin!(debounce);
+        loop {
+            tokio::select! {
+                _ = &mut debounce => break,
+                event = rx.recv() => match event {
+                    Some(event) => record_path_event(&mut path_events, event),
+                    None => break,
+                },
+            }
+        }
+
+        println!("Content changed, updating {} path(s)...", path_events.len());
+        for (path, kind) in &path_events {
+            if !path.extension().map(|e| e == "md").unwrap_or(false) {
+                continue;
+            }
+            let Some(slug) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            // Only a genuine Remove event (or a non-remove event whose file
+            // has since vanished) deletes a cache entry — a sibling file
+            // merely sharing the stem (e.g. a `.md~` backup) must not.
+            if matches!(kind, EventKind::Remove(_)) {
+                post_cache.lock().unwrap().remove(&slug);
+                continue;
+            }
+            match parse_post(path, &highlight_config, &markdown_options) {
+                Some(post) => {
+                    post_cache.lock().unwrap().insert(slug, post);
+                }
+                None if !path.exists() => {
+                    post_cache.lock().unwrap().remove(&slug);
+                }
+                None => {}
+            }
         }
+
+        // Re-resolve wikilinks over every cached post: changed file(s) carry
+        // fresh sentinels from parse_post (already-resolved posts are a
+        // no-op here), and backlinks are rebuilt from each post's
+        // structured `wikilink_targets` against the current slug set.
+        let (new_aliases, new_tags, new_index) = {
+            let mut cache = post_cache.lock().unwrap();
+            let mut posts_vec: Vec<Post> = cache.drain().map(|(_, post)| post).collect();
+            resolve_wikilinks(&mut posts_vec);
+            for post in posts_vec {
+                cache.insert(post.slug.clone(), post);
+            }
+            (build_alias_map(&cache), build_tag_map(&cache), build_search_index(&cache))
+        };
+        *alias_cache.lock().unwrap() = new_aliases;
+        *tag_cache.lock().unwrap() = new_tags;
+        *search_index.lock().unwrap() = new_index;
         println!("Posts reloaded!");
     }
 }
 
 async fn home(
     posts: web::Data<Arc<Mutex<HashMap<String, Post>>>>,
+    tags: web::Data<TagCache>,
     tera: web::Data<Tera>,
 ) -> Result<HttpResponse> {
     let posts_guard = posts.lock().unwrap();
     let mut context = Context::new();
-    let mut posts_vec: Vec<&Post> = posts_guard.values().collect();
-    posts_vec.sort_by(|a, b| b.frontmatter.date.cmp(&a.frontmatter.date));
+    let posts_vec = sorted_posts(&posts_guard);
     context.insert("posts", &posts_vec);
-    
-    // Get all unique tags
-    let mut all_tags = std::collections::HashSet::new();
-    for post in &posts_vec {
-        if let Some(tags) = &post.frontmatter.tags {
-            for tag in tags {
-                all_tags.insert(tag.clone());
-            }
-        }
-    }
-    let mut tags_vec: Vec<String> = all_tags.into_iter().collect();
+
+    let mut tags_vec: Vec<String> = tags.lock().unwrap().keys().cloned().collect();
     tags_vec.sort();
     context.insert("all_tags", &tags_vec);
-    
+
     match tera.render("home.html", &context) {
         Ok(rendered) => Ok(HttpResponse::Ok().content_type("text/html").body(rendered)),
         Err(_) => Ok(HttpResponse::Ok().content_type("text/html").body(
@@ -211,13 +795,26 @@ async fn post_detail(
         
         match tera.render("post.html", &context) {
             Ok(rendered) => Ok(HttpResponse::Ok().content_type("text/html").body(rendered)),
-            Err(_) => Ok(HttpResponse::Ok().content_type("text/html").body(
-                format!("<h1>{}</h1><p>Date: {}</p><div>{}</div>",
-                    post.frontmatter.title,
-                    post.frontmatter.date,
-                    post.html
-                )
-            ))
+            Err(_) => {
+                let backlinks_html = if post.backlinks.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "<h2>Linked from</h2><ul>{}</ul>",
+                        post.backlinks.iter().map(|slug| format!(
+                            r#"<li><a href="/posts/{0}">{0}</a></li>"#, slug
+                        )).collect::<Vec<_>>().join("")
+                    )
+                };
+                Ok(HttpResponse::Ok().content_type("text/html").body(
+                    format!("<h1>{}</h1><p>Date: {}</p><div>{}</div>{}",
+                        post.frontmatter.title,
+                        post.frontmatter.date,
+                        post.html,
+                        backlinks_html
+                    )
+                ))
+            }
         }
     } else {
         // Try to render 404 template, fall back to basic HTML
@@ -228,6 +825,291 @@ async fn post_detail(
     }
 }
 
+async fn alias_fallback(
+    req: HttpRequest,
+    aliases: web::Data<Arc<Mutex<HashMap<String, String>>>>,
+    tera: web::Data<Tera>,
+) -> Result<HttpResponse> {
+    let path = req.path().trim_start_matches('/');
+    let target_slug = aliases.lock().unwrap().get(path).cloned();
+
+    if let Some(slug) = target_slug {
+        return Ok(HttpResponse::MovedPermanently()
+            .insert_header(("Location", format!("/posts/{}", slug)))
+            .finish());
+    }
+
+    match tera.render("404.html", &Context::new()) {
+        Ok(rendered) => Ok(HttpResponse::NotFound().content_type("text/html").body(rendered)),
+        Err(_) => Ok(HttpResponse::NotFound().content_type("text/html").body("<h1>404 - Not Found</h1>")),
+    }
+}
+
+fn escape_xml(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// TF-IDF weight applied to a title-term match, relative to a body-term
+/// match, when building the inverted index's per-document term frequencies.
+const TITLE_TERM_WEIGHT: usize = 3;
+
+/// Splits text into lowercased alphanumeric terms, the same tokenization
+/// used to build the index and to parse incoming search queries.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+/// In-memory inverted index over every published post's title and body,
+/// supporting TF-IDF ranked search instead of a linear `contains` scan.
+#[derive(Debug, Default)]
+struct SearchIndex {
+    /// term -> (slug, term_frequency) postings list.
+    postings: HashMap<String, Vec<(String, usize)>>,
+    doc_count: usize,
+}
+
+impl SearchIndex {
+    /// Scores and ranks posts against a query, highest TF-IDF score first.
+    /// Unknown query terms simply contribute no postings.
+    fn search(&self, query: &str) -> Vec<String> {
+        if self.doc_count == 0 {
+            return Vec::new();
+        }
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        for term in tokenize(query) {
+            let Some(postings) = self.postings.get(&term) else {
+                continue;
+            };
+            let df = postings.len();
+            if df == 0 {
+                continue;
+            }
+            let idf = (self.doc_count as f64 / df as f64).ln();
+            for (slug, tf) in postings {
+                let tf_weight = 1.0 + (*tf as f64).ln();
+                *scores.entry(slug.clone()).or_insert(0.0) += tf_weight * idf;
+            }
+        }
+        let mut ranked: Vec<(String, f64)> = scores.into_iter().collect();
+        // Break score ties by slug so result order is stable across runs:
+        // `scores` is a HashMap, whose iteration order is randomized per
+        // process, and without a secondary key equal-scoring posts could
+        // swap places on every reload.
+        ranked.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        ranked.into_iter().map(|(slug, _score)| slug).collect()
+    }
+}
+
+/// Builds the inverted index from every published post's title (weighted
+/// higher) and body content, for TF-IDF ranked search.
+fn build_search_index(post_cache: &HashMap<String, Post>) -> SearchIndex {
+    let mut postings: HashMap<String, Vec<(String, usize)>> = HashMap::new();
+    let mut doc_count = 0;
+
+    for post in post_cache.values().filter(|post| post.is_published()) {
+        doc_count += 1;
+        let mut term_freq: HashMap<String, usize> = HashMap::new();
+        for term in tokenize(&post.frontmatter.title) {
+            *term_freq.entry(term).or_insert(0) += TITLE_TERM_WEIGHT;
+        }
+        for term in tokenize(&post.content) {
+            *term_freq.entry(term).or_insert(0) += 1;
+        }
+        for (term, freq) in term_freq {
+            postings.entry(term).or_default().push((post.slug.clone(), freq));
+        }
+    }
+
+    SearchIndex { postings, doc_count }
+}
+
+fn sorted_posts(posts_guard: &HashMap<String, Post>) -> Vec<&Post> {
+    let mut posts_vec: Vec<&Post> = posts_guard
+        .values()
+        .filter(|post| post.is_published())
+        .collect();
+    posts_vec.sort_by_key(|post| std::cmp::Reverse(post.frontmatter.published_at()));
+    posts_vec
+}
+
+fn rss_items_xml(posts: &[&Post]) -> String {
+    posts
+        .iter()
+        .map(|post| {
+            let url = format!("{}/posts/{}", SITE_URL, post.slug);
+            format!(
+                "<item><title>{}</title><link>{}</link><guid>{}</guid><pubDate>{}</pubDate></item>",
+                escape_xml(&post.frontmatter.title),
+                escape_xml(&url),
+                escape_xml(&url),
+                post.frontmatter.published_at().to_rfc2822(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+async fn rss_feed(posts: web::Data<Arc<Mutex<HashMap<String, Post>>>>) -> Result<HttpResponse> {
+    let posts_guard = posts.lock().unwrap();
+    let posts_vec = sorted_posts(&posts_guard);
+    let body = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><rss version="2.0"><channel><title>Blog</title><link>{0}</link><description>Latest posts</description>{1}</channel></rss>"#,
+        SITE_URL,
+        rss_items_xml(&posts_vec),
+    );
+    Ok(HttpResponse::Ok().content_type("application/rss+xml").body(body))
+}
+
+async fn tag_feed(
+    path: web::Path<String>,
+    posts: web::Data<Arc<Mutex<HashMap<String, Post>>>>,
+) -> Result<HttpResponse> {
+    let tag = path.into_inner();
+    let posts_guard = posts.lock().unwrap();
+    let posts_vec: Vec<&Post> = sorted_posts(&posts_guard)
+        .into_iter()
+        .filter(|post| {
+            post.frontmatter
+                .tags
+                .as_ref()
+                .map(|tags| tags.iter().any(|t| t == &tag))
+                .unwrap_or(false)
+        })
+        .collect();
+    let body = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><rss version="2.0"><channel><title>Blog: {0}</title><link>{1}/tags/{0}</link><description>Posts tagged {0}</description>{2}</channel></rss>"#,
+        escape_xml(&tag),
+        SITE_URL,
+        rss_items_xml(&posts_vec),
+    );
+    Ok(HttpResponse::Ok().content_type("application/rss+xml").body(body))
+}
+
+async fn tags_index(
+    tags: web::Data<TagCache>,
+    tera: web::Data<Tera>,
+) -> Result<HttpResponse> {
+    let tags_guard = tags.lock().unwrap();
+    let mut tag_counts: Vec<(String, usize)> = tags_guard
+        .iter()
+        .map(|(tag, slugs)| (tag.clone(), slugs.len()))
+        .collect();
+    tag_counts.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut context = Context::new();
+    context.insert("tags", &tag_counts);
+
+    match tera.render("tags.html", &context) {
+        Ok(rendered) => Ok(HttpResponse::Ok().content_type("text/html").body(rendered)),
+        Err(_) => Ok(HttpResponse::Ok().content_type("text/html").body(
+            format!("<h1>Tags</h1><ul>{}</ul>",
+                tag_counts.iter().map(|(tag, count)| format!(
+                    r#"<li><a href="/tags/{0}">{0}</a> ({1})</li>"#,
+                    tag, count
+                )).collect::<Vec<_>>().join("")
+            )
+        ))
+    }
+}
+
+async fn tag_archive(
+    path: web::Path<String>,
+    tags: web::Data<TagCache>,
+    posts: web::Data<Arc<Mutex<HashMap<String, Post>>>>,
+    tera: web::Data<Tera>,
+) -> Result<HttpResponse> {
+    let tag = path.into_inner();
+    let tags_guard = tags.lock().unwrap();
+    let posts_guard = posts.lock().unwrap();
+    let posts_for_tag: Vec<&Post> = tags_guard
+        .get(&tag)
+        .map(|slugs| slugs.iter().filter_map(|slug| posts_guard.get(slug)).collect())
+        .unwrap_or_default();
+
+    let mut context = Context::new();
+    context.insert("tag", &tag);
+    context.insert("posts", &posts_for_tag);
+
+    match tera.render("tag.html", &context) {
+        Ok(rendered) => Ok(HttpResponse::Ok().content_type("text/html").body(rendered)),
+        Err(_) => Ok(HttpResponse::Ok().content_type("text/html").body(
+            format!("<h1>Tag: {}</h1><ul>{}</ul>", escape_xml(&tag),
+                posts_for_tag.iter().map(|p| format!(
+                    r#"<li><a href="/posts/{}">{}</a> - {}</li>"#,
+                    p.slug, p.frontmatter.title, p.frontmatter.date
+                )).collect::<Vec<_>>().join("")
+            )
+        ))
+    }
+}
+
+async fn atom_feed(posts: web::Data<Arc<Mutex<HashMap<String, Post>>>>) -> Result<HttpResponse> {
+    let posts_guard = posts.lock().unwrap();
+    let posts_vec = sorted_posts(&posts_guard);
+    let updated = posts_vec
+        .first()
+        .map(|p| p.frontmatter.published_at().to_rfc3339())
+        .unwrap_or_default();
+    let entries = posts_vec
+        .iter()
+        .map(|post| {
+            let url = format!("{}/posts/{}", SITE_URL, post.slug);
+            format!(
+                r#"<entry><title>{}</title><link href="{}" /><id>{}</id><updated>{}</updated><content type="html">{}</content></entry>"#,
+                escape_xml(&post.frontmatter.title),
+                escape_xml(&url),
+                escape_xml(&url),
+                post.frontmatter.published_at().to_rfc3339(),
+                escape_xml(&post.html),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("");
+    let body = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><feed xmlns="http://www.w3.org/2005/Atom"><title>Blog</title><link href="{0}/" /><id>{0}/</id><updated>{1}</updated>{2}</feed>"#,
+        SITE_URL,
+        updated,
+        entries,
+    );
+    Ok(HttpResponse::Ok().content_type("application/atom+xml").body(body))
+}
+
+async fn json_feed(posts: web::Data<Arc<Mutex<HashMap<String, Post>>>>) -> Result<HttpResponse> {
+    let posts_guard = posts.lock().unwrap();
+    let posts_vec = sorted_posts(&posts_guard);
+    let items = posts_vec
+        .iter()
+        .map(|post| JsonFeedItem {
+            id: format!("{}/posts/{}", SITE_URL, post.slug),
+            url: format!("{}/posts/{}", SITE_URL, post.slug),
+            title: post.frontmatter.title.clone(),
+            content_html: post.html.clone(),
+            date_published: post.frontmatter.published_at().to_rfc3339(),
+        })
+        .collect();
+    let feed = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1".to_string(),
+        title: "Blog".to_string(),
+        home_page_url: SITE_URL.to_string(),
+        feed_url: format!("{}/feed.json", SITE_URL),
+        items,
+    };
+    let body = serde_json::to_string(&feed).unwrap_or_default();
+    Ok(HttpResponse::Ok().content_type("application/feed+json").body(body))
+}
+
 async fn about(tera: web::Data<Tera>) -> Result<HttpResponse> {
     match tera.render("about.html", &Context::new()) {
         Ok(rendered) => Ok(HttpResponse::Ok().content_type("text/html").body(rendered)),
@@ -240,22 +1122,26 @@ async fn about(tera: web::Data<Tera>) -> Result<HttpResponse> {
 async fn search(
     query: web::Query<SearchQuery>,
     posts: web::Data<Arc<Mutex<HashMap<String, Post>>>>,
+    tags: web::Data<TagCache>,
+    search_index: web::Data<Arc<Mutex<SearchIndex>>>,
     tera: web::Data<Tera>,
 ) -> Result<HttpResponse> {
     let posts_guard = posts.lock().unwrap();
-    let mut filtered_posts: Vec<&Post> = posts_guard.values().collect();
-    
-    // Filter by search query
-    if let Some(search_term) = &query.q {
-        if !search_term.trim().is_empty() {
-            let search_lower = search_term.to_lowercase();
-            filtered_posts.retain(|post| {
-                post.frontmatter.title.to_lowercase().contains(&search_lower) ||
-                post.content.to_lowercase().contains(&search_lower)
-            });
-        }
-    }
-    
+
+    // Rank by TF-IDF when there's a query; otherwise fall back to date order.
+    let query_term = query.q.as_deref().unwrap_or("").trim();
+    let mut filtered_posts: Vec<&Post> = if query_term.is_empty() {
+        sorted_posts(&posts_guard)
+    } else {
+        search_index
+            .lock()
+            .unwrap()
+            .search(query_term)
+            .into_iter()
+            .filter_map(|slug| posts_guard.get(&slug))
+            .collect()
+    };
+
     // Filter by tag
     if let Some(tag) = &query.tag {
         if !tag.trim().is_empty() {
@@ -266,25 +1152,14 @@ async fn search(
             });
         }
     }
-    
-    // Sort by date (newest first)
-    filtered_posts.sort_by(|a, b| b.frontmatter.date.cmp(&a.frontmatter.date));
-    
+
     let mut context = Context::new();
     context.insert("posts", &filtered_posts);
     context.insert("search_query", &query.q);
     context.insert("selected_tag", &query.tag);
     
-    // Get all unique tags for the filter dropdown
-    let mut all_tags = std::collections::HashSet::new();
-    for post in posts_guard.values() {
-        if let Some(tags) = &post.frontmatter.tags {
-            for tag in tags {
-                all_tags.insert(tag.clone());
-            }
-        }
-    }
-    let mut tags_vec: Vec<String> = all_tags.into_iter().collect();
+    // Tags for the filter dropdown
+    let mut tags_vec: Vec<String> = tags.lock().unwrap().keys().cloned().collect();
     tags_vec.sort();
     context.insert("all_tags", &tags_vec);
     
@@ -307,3 +1182,308 @@ async fn search(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_post(slug: &str, title: &str, content: &str) -> Post {
+        Post {
+            slug: slug.to_string(),
+            frontmatter: FrontMatter {
+                title: title.to_string(),
+                date: "2024-01-01".to_string(),
+                tags: None,
+                draft: None,
+                aliases: None,
+            },
+            content: content.to_string(),
+            html: content.to_string(),
+            toc: Vec::new(),
+            backlinks: Vec::new(),
+            wikilink_targets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn search_ranks_rarer_terms_and_title_matches_higher() {
+        // A third, unrelated post is required here: if every indexed post
+        // contained "rust" (document frequency == doc_count), idf would be
+        // ln(doc_count/doc_count) == 0 and both matching posts would score
+        // exactly 0.0, making the assertion below depend on the slug
+        // tie-break instead of on the ranking logic under test.
+        let post_cache: HashMap<String, Post> = [
+            test_post("rust-post", "Rust", "rust is great, rust rust rust"),
+            test_post("other-post", "Other", "this post only mentions rust once"),
+            test_post("unrelated-post", "Gardening", "tips for growing tomatoes"),
+        ]
+        .into_iter()
+        .map(|post| (post.slug.clone(), post))
+        .collect();
+
+        let index = build_search_index(&post_cache);
+        let results = index.search("rust");
+
+        // Both posts contain "rust", but rust-post has a much higher term
+        // frequency and also matches in the (more heavily weighted) title,
+        // so it must rank first.
+        assert_eq!(results.first().map(String::as_str), Some("rust-post"));
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn search_breaks_score_ties_by_slug() {
+        // Three posts that mention "rust" with identical term frequency and
+        // title weighting tie on TF-IDF score; the ranking must still be
+        // deterministic (alphabetical by slug) rather than depend on
+        // `HashMap` iteration order.
+        let post_cache: HashMap<String, Post> = [
+            test_post("charlie", "Post", "rust"),
+            test_post("alpha", "Post", "rust"),
+            test_post("bravo", "Post", "rust"),
+        ]
+        .into_iter()
+        .map(|post| (post.slug.clone(), post))
+        .collect();
+
+        let index = build_search_index(&post_cache);
+        let results = index.search("rust");
+
+        assert_eq!(results, vec!["alpha", "bravo", "charlie"]);
+    }
+
+    #[test]
+    fn search_ignores_terms_not_in_the_index() {
+        let post_cache: HashMap<String, Post> =
+            [test_post("only-post", "Only", "hello world")]
+                .into_iter()
+                .map(|post| (post.slug.clone(), post))
+                .collect();
+
+        let index = build_search_index(&post_cache);
+        assert!(index.search("nonexistent").is_empty());
+    }
+
+    #[test]
+    fn parse_post_resolves_real_wikilink_markdown_end_to_end() {
+        // Regression test for a bug where wikilinks never resolved on any
+        // real post: pulldown-cmark's inline scanner splits `[[`/`]]` into
+        // separate `[`/`[`/`]`/`]` text events, so matching `"[["` within a
+        // single `Event::Text` (as the first implementation did) never
+        // fired. This drives an actual `.md` file through `parse_post`
+        // instead of constructing a `Post` by hand, so it would have caught
+        // that regression.
+        let dir = std::env::temp_dir().join(format!("md_blog_wikilink_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("create temp content dir");
+        let path = dir.join("post-a.md");
+        fs::write(
+            &path,
+            "---\ntitle: A\ndate: 2024-01-01\n---\nSee [[post-b|Post B]] for more.\n",
+        )
+        .expect("write temp post");
+
+        let highlight_config = HighlightConfig::default();
+        let markdown_options = MarkdownOptions::default();
+        let post_a = parse_post(&path, &highlight_config, &markdown_options).expect("post parses");
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(post_a.wikilink_targets, vec!["post-b".to_string()]);
+
+        let post_b = test_post("post-b", "B", "nothing");
+        let mut posts = vec![post_a, post_b];
+        resolve_wikilinks(&mut posts);
+
+        assert!(posts[0].html.contains(r#"<a href="/posts/post-b" class="wikilink">Post B</a>"#));
+        assert_eq!(posts[1].backlinks, vec!["post-a".to_string()]);
+    }
+
+    #[test]
+    fn resolve_wikilinks_links_known_slugs_and_tracks_backlinks() {
+        let mut a = test_post("post-a", "A", "see other");
+        a.html = format!(
+            "before {}post-b{}Post B{} after",
+            WIKILINK_OPEN, WIKILINK_SEP, WIKILINK_CLOSE
+        );
+        a.wikilink_targets = vec!["post-b".to_string()];
+        let b = test_post("post-b", "B", "nothing");
+
+        let mut posts = vec![a, b];
+        resolve_wikilinks(&mut posts);
+
+        assert!(posts[0].html.contains(r#"<a href="/posts/post-b" class="wikilink">Post B</a>"#));
+        assert_eq!(posts[1].backlinks, vec!["post-a".to_string()]);
+        assert!(posts[0].backlinks.is_empty());
+    }
+
+    #[test]
+    fn resolve_wikilinks_marks_unknown_slug_as_broken_and_escapes_html() {
+        let mut post = test_post("post-a", "A", "see other");
+        post.html = format!(
+            "{}missing{}<script>alert(1)</script>{}",
+            WIKILINK_OPEN, WIKILINK_SEP, WIKILINK_CLOSE
+        );
+        post.wikilink_targets = vec!["missing".to_string()];
+
+        let mut posts = vec![post];
+        resolve_wikilinks(&mut posts);
+
+        assert!(posts[0].html.contains("wikilink-broken"));
+        assert!(!posts[0].html.contains("<script>"));
+        assert!(posts[0].html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn resolve_wikilinks_does_not_panic_on_unpaired_sentinel() {
+        let mut post = test_post("post-a", "A", "stray sentinel");
+        post.html = format!("oops {} no pair here", WIKILINK_OPEN);
+
+        let mut posts = vec![post];
+        resolve_wikilinks(&mut posts);
+
+        assert!(posts[0].html.contains("no pair here"));
+    }
+
+    #[test]
+    fn build_tag_map_groups_published_posts_newest_first_and_excludes_drafts() {
+        let mut old_post = test_post("old-post", "Old", "body");
+        old_post.frontmatter.tags = Some(vec!["rust".to_string()]);
+        old_post.frontmatter.date = "2023-01-01".to_string();
+
+        let mut new_post = test_post("new-post", "New", "body");
+        new_post.frontmatter.tags = Some(vec!["rust".to_string()]);
+        new_post.frontmatter.date = "2024-01-01".to_string();
+
+        let mut draft_post = test_post("draft-post", "Draft", "body");
+        draft_post.frontmatter.tags = Some(vec!["rust".to_string()]);
+        draft_post.frontmatter.draft = Some(true);
+
+        let post_cache: HashMap<String, Post> = [old_post, new_post, draft_post]
+            .into_iter()
+            .map(|p| (p.slug.clone(), p))
+            .collect();
+
+        let tags = build_tag_map(&post_cache);
+
+        assert_eq!(tags.get("rust"), Some(&vec!["new-post".to_string(), "old-post".to_string()]));
+    }
+
+    #[test]
+    fn parse_post_dedupes_heading_anchor_ids() {
+        let dir = std::env::temp_dir().join(format!("md_blog_toc_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("create temp content dir");
+        let path = dir.join("post-a.md");
+        fs::write(
+            &path,
+            "---\ntitle: A\ndate: 2024-01-01\n---\n## Intro\ntext\n## Intro\nmore text\n",
+        )
+        .expect("write temp post");
+
+        let highlight_config = HighlightConfig::default();
+        let markdown_options = MarkdownOptions::default();
+        let post = parse_post(&path, &highlight_config, &markdown_options).expect("post parses");
+        fs::remove_dir_all(&dir).ok();
+
+        let ids: Vec<&str> = post.toc.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, vec!["intro", "intro-2"]);
+    }
+
+    #[test]
+    fn parse_post_slugifies_heading_text_around_a_wikilink_correctly() {
+        // Regression test: collect_heading_texts used to run over the
+        // sentinel-rewritten markdown_source instead of the original
+        // content, so a heading containing a wikilink slugified the
+        // sentinel-wrapped target+display text merged together instead of
+        // the heading's actual words.
+        let dir = std::env::temp_dir().join(format!("md_blog_toc_wikilink_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("create temp content dir");
+        let path = dir.join("post-a.md");
+        fs::write(
+            &path,
+            "---\ntitle: A\ndate: 2024-01-01\n---\n## See [[hello]] here\ntext\n",
+        )
+        .expect("write temp post");
+
+        let highlight_config = HighlightConfig::default();
+        let markdown_options = MarkdownOptions::default();
+        let post = parse_post(&path, &highlight_config, &markdown_options).expect("post parses");
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(post.toc.len(), 1);
+        assert_eq!(post.toc[0].id, "see-hello-here");
+        assert!(post.html.contains(r#"<h2 id="see-hello-here">"#));
+    }
+
+    #[test]
+    fn record_path_event_keeps_the_most_recent_kind_per_path() {
+        use notify::{Event, EventKind};
+
+        let path = PathBuf::from("content/post-a.md");
+        let mut path_events: HashMap<PathBuf, EventKind> = HashMap::new();
+
+        record_path_event(&mut path_events, Event::new(EventKind::Create(notify::event::CreateKind::File)).add_path(path.clone()));
+        record_path_event(&mut path_events, Event::new(EventKind::Remove(notify::event::RemoveKind::File)).add_path(path.clone()));
+
+        // A later Modify/Create must not be shadowed by an earlier Remove
+        // within the same debounce window, and vice versa — only the most
+        // recent event for a path should survive.
+        assert!(matches!(path_events.get(&path), Some(EventKind::Remove(_))));
+
+        record_path_event(&mut path_events, Event::new(EventKind::Modify(notify::event::ModifyKind::Any)).add_path(path.clone()));
+        assert!(matches!(path_events.get(&path), Some(EventKind::Modify(_))));
+    }
+
+    #[test]
+    fn highlight_code_falls_back_to_plain_text_for_unknown_language() {
+        let config = HighlightConfig::default();
+        let html = highlight_code("fn main() {}", "not-a-real-language", &config);
+
+        assert!(html.contains("fn main"));
+    }
+
+    #[test]
+    fn build_alias_map_strips_leading_slash_and_maps_to_current_slug() {
+        let mut post = test_post("renamed-post", "Renamed", "body");
+        post.frontmatter.aliases = Some(vec!["/old-slug".to_string(), "another-old-slug".to_string()]);
+        let post_cache: HashMap<String, Post> = [post].into_iter().map(|p| (p.slug.clone(), p)).collect();
+
+        let aliases = build_alias_map(&post_cache);
+
+        assert_eq!(aliases.get("old-slug").map(String::as_str), Some("renamed-post"));
+        assert_eq!(aliases.get("another-old-slug").map(String::as_str), Some("renamed-post"));
+    }
+
+    #[test]
+    fn is_published_hides_drafts_and_future_dated_posts() {
+        let mut draft = test_post("draft-post", "Draft", "body");
+        draft.frontmatter.draft = Some(true);
+        assert!(!draft.is_published());
+
+        let mut scheduled = test_post("scheduled-post", "Scheduled", "body");
+        scheduled.frontmatter.date = "2999-01-01".to_string();
+        assert!(!scheduled.is_published());
+
+        let published = test_post("published-post", "Published", "body");
+        assert!(published.is_published());
+    }
+
+    #[test]
+    fn parse_date_falls_back_to_now_on_unparsable_date_instead_of_panicking() {
+        let before = Local::now();
+        let parsed = parse_date("not a real date");
+        let after = Local::now();
+
+        assert!(parsed >= before && parsed <= after);
+    }
+
+    #[test]
+    fn rss_items_xml_emits_rfc2822_pub_date_not_raw_frontmatter_date() {
+        // Regression test: pubDate used to echo the raw frontmatter `date`
+        // string verbatim, which isn't valid RFC-822 for bare dates like
+        // "2024-01-01" (see the "emit spec-conforming feed timestamps" fix).
+        let post = test_post("post-a", "A", "body");
+        let xml = rss_items_xml(&[&post]);
+
+        assert!(!xml.contains("<pubDate>2024-01-01</pubDate>"));
+        assert!(xml.contains(&post.frontmatter.published_at().to_rfc2822()));
+    }
+}